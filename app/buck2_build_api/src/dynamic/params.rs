@@ -40,7 +40,7 @@ pub struct DynamicLambdaStaticFields {
     pub dynamic: IndexSet<Artifact>,
     /// Things I produce
     pub outputs: Box<[BuildArtifact]>,
-    /// Execution platform inherited from the owner to use for actionsfbcode/buck2/app/buck2_action_impl/src/dynamic/deferred.rs
+    /// Execution platform inherited from the owner to use for actions.
     pub execution_platform: ExecutionPlatformResolution,
 }
 
@@ -50,6 +50,14 @@ pub struct DynamicLambdaParams<'v> {
     pub plugins: Option<ValueTypedComplex<'v, AnalysisPlugins<'v>>>,
     pub lambda: StarlarkCallable<'v>,
     pub arg: Option<Value<'v>>,
+    /// Whether this lambda was registered as fallible, i.e. as one that may return a
+    /// `bxl.Result`-shaped value (`Ok`/`Err`) rather than produce every declared output.
+    ///
+    /// This struct only records the flag and carries it through freezing into
+    /// [`FrozenDynamicLambdaParams::fallible`]. Nothing here reads it: an `Err` returned by the
+    /// lambda is not recorded against individual `outputs`, and setting it does not change how
+    /// a failing lambda is handled.
+    pub fallible: bool,
     pub static_fields: DynamicLambdaStaticFields,
 }
 
@@ -59,6 +67,8 @@ pub struct FrozenDynamicLambdaParams {
     pub(crate) plugins: Option<FrozenValueTyped<'static, FrozenAnalysisPlugins>>,
     pub lambda: FrozenStarlarkCallable,
     pub arg: Option<FrozenValue>,
+    /// See [`DynamicLambdaParams::fallible`].
+    pub fallible: bool,
     pub static_fields: DynamicLambdaStaticFields,
 }
 
@@ -104,6 +114,7 @@ impl<'v> Freeze for DynamicLambdaParams<'v> {
             plugins: self.plugins.freeze(freezer)?,
             lambda: self.lambda.freeze(freezer)?,
             arg: self.arg.freeze(freezer)?,
+            fallible: self.fallible,
             static_fields: self.static_fields,
         })
     }
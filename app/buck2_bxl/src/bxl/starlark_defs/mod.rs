@@ -0,0 +1,62 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+use starlark::environment::GlobalsBuilder;
+
+mod result;
+
+/// Registers the free functions exposed to every BXL script (alongside the `bxl.Result` /
+/// `bxl.Error` types, which register themselves via `#[starlark_value]`).
+pub(crate) fn register_bxl_natives(globals: &mut GlobalsBuilder) {
+    result::register_bxl_try(globals);
+}
+
+#[cfg(test)]
+mod tests {
+    use starlark::environment::Globals;
+    use starlark::environment::GlobalsBuilder;
+    use starlark::environment::Module;
+    use starlark::eval::Evaluator;
+    use starlark::syntax::AstModule;
+    use starlark::syntax::Dialect;
+
+    use super::register_bxl_natives;
+
+    fn globals() -> Globals {
+        GlobalsBuilder::standard().with(register_bxl_natives).build()
+    }
+
+    #[test]
+    fn bxl_try_is_reachable_under_the_bxl_namespace() {
+        let ast = AstModule::parse(
+            "test.bxl",
+            "bxl.try(lambda: 1).unwrap()".to_owned(),
+            &Dialect::Extended,
+        )
+        .unwrap();
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+        let result = eval.eval_module(ast, &globals()).unwrap();
+        assert_eq!(result.unpack_i32(), Some(1));
+    }
+
+    #[test]
+    fn bxl_try_catches_a_raise() {
+        let ast = AstModule::parse(
+            "test.bxl",
+            "bxl.try(lambda: fail(\"boom\")).is_ok()".to_owned(),
+            &Dialect::Extended,
+        )
+        .unwrap();
+        let module = Module::new();
+        let mut eval = Evaluator::new(&module);
+        let result = eval.eval_module(ast, &globals()).unwrap();
+        assert_eq!(result.unpack_bool(), Some(false));
+    }
+}
@@ -12,18 +12,23 @@ use derivative::Derivative;
 use derive_more::Display;
 use display_container::fmt_container;
 use dupe::Dupe;
+use serde::ser::SerializeMap;
+use serde::Serialize;
+use serde::Serializer;
 use starlark::any::ProvidesStaticType;
+use starlark::environment::GlobalsBuilder;
 use starlark::environment::Methods;
 use starlark::environment::MethodsBuilder;
 use starlark::environment::MethodsStatic;
+use starlark::eval::Evaluator;
 use starlark::starlark_complex_values;
 use starlark::starlark_module;
 use starlark::starlark_simple_value;
 use starlark::values::starlark_value;
 use starlark::values::string::StarlarkStr;
+use starlark::values::typing::StarlarkCallable;
 use starlark::values::Freeze;
 use starlark::values::FrozenValue;
-use starlark::values::NoSerialize;
 use starlark::values::StarlarkValue;
 use starlark::values::Trace;
 use starlark::values::Value;
@@ -37,6 +42,8 @@ enum BxlResultError {
     UnwrapOnError(buck2_error::Error),
     #[error("called `bxl.Result.unwrap_err()` on an `Ok` value: {0}")]
     UnwrapErrOnOk(String),
+    #[error("expected callback to return a `bxl.Result`, got: {0}")]
+    ExpectedResult(String),
 }
 
 /// Error value object returned by fallible BXL operation.
@@ -45,8 +52,6 @@ enum BxlResultError {
     ProvidesStaticType,
     Derivative,
     Display,
-    // TODO(nero): implement Serialize for StarlarkError
-    NoSerialize,
     Allocative,
     StarlarkDocs,
     Trace
@@ -73,20 +78,109 @@ fn error_methods(builder: &mut MethodsBuilder) {
     /// The error message
     #[starlark(attribute)]
     fn message<'v>(this: &'v StarlarkError) -> anyhow::Result<String> {
-        Ok(format!("{:?}", this.err))
+        Ok(this.message())
+    }
+
+    /// The error's category: `"user"` for errors categorized as user errors, `"infra"` for
+    /// every other error, including ones that carry no category at all.
+    #[starlark(attribute)]
+    fn category<'v>(this: &'v StarlarkError) -> anyhow::Result<String> {
+        Ok(this.category())
+    }
+
+    /// The tags attached to the underlying `buck2_error::Error`, e.g. `"IoNotFound"`,
+    /// useful for aggregating failures without string-matching `message`.
+    #[starlark(attribute)]
+    fn tags<'v>(this: &'v StarlarkError) -> anyhow::Result<Vec<String>> {
+        Ok(this.tags())
+    }
+
+    /// The error's severity: currently always `"error"`. Every `bxl.Error` is a failure that
+    /// was captured into a `bxl.Result` rather than left to abort the script, so it's never merely
+    /// advisory -- unlike the starlark binary's `EvalSeverity`, there's no lesser-severity
+    /// tier to surface here. Use `category` to tell a user-actionable failure (CI should
+    /// gate on it) apart from an infra one.
+    #[starlark(attribute)]
+    fn severity<'v>(this: &'v StarlarkError) -> anyhow::Result<String> {
+        Ok(this.severity())
+    }
+
+    /// Render the error for logging. In compact mode (`multiline = False`) this is the same single
+    /// line as `message`. In multiline mode, a header line naming the error's source location
+    /// (when it has one) is printed, underlined end-to-end -- there's no sub-span to point at,
+    /// only the location string itself -- followed by the error chain, one cause per line,
+    /// indented by depth.
+    fn render<'v>(
+        this: &'v StarlarkError,
+        #[starlark(require = pos)] multiline: bool,
+    ) -> anyhow::Result<String> {
+        Ok(this.render(multiline))
     }
 }
 
-#[derive(
-    Debug,
-    // TODO(nero): implement Serialize for StarlarkResult
-    NoSerialize,
-    Trace,
-    Freeze,
-    StarlarkDocs,
-    ProvidesStaticType,
-    Allocative
-)]
+/// JSON-serializable projection of a `bxl.Error`. Its fields are populated from the exact
+/// same `StarlarkError::message`/`category`/`tags`/`severity` helpers that back the Starlark
+/// attributes of the same names, so the two representations can't drift apart.
+#[derive(Serialize)]
+struct SerializedError {
+    message: String,
+    category: String,
+    tags: Vec<String>,
+    severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_location: Option<String>,
+}
+
+impl StarlarkError {
+    fn message(&self) -> String {
+        format!("{:?}", self.err)
+    }
+
+    fn category(&self) -> String {
+        match self.err.category() {
+            Some(buck2_error::Category::User) => "user",
+            Some(_) | None => "infra",
+        }
+        .to_owned()
+    }
+
+    fn tags(&self) -> Vec<String> {
+        self.err.tags().iter().map(|tag| tag.to_string()).collect()
+    }
+
+    fn severity(&self) -> String {
+        // Every `bxl.Error` already represents a caught failure, so it's always an `"error"`
+        // regardless of `category` -- see the doc comment on `error_methods::severity` for why
+        // this isn't derived from `category` the way `"user"`/`"infra"` is.
+        "error".to_owned()
+    }
+
+    /// See [`error_methods::render`] for the user-facing contract.
+    fn render(&self, multiline: bool) -> String {
+        render_error(&self.err, multiline)
+    }
+
+    fn to_serialized(&self) -> SerializedError {
+        SerializedError {
+            message: self.message(),
+            category: self.category(),
+            tags: self.tags(),
+            severity: self.severity(),
+            source_location: self.err.source_location().map(ToOwned::to_owned),
+        }
+    }
+}
+
+impl Serialize for StarlarkError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_serialized().serialize(serializer)
+    }
+}
+
+#[derive(Debug, Trace, Freeze, StarlarkDocs, ProvidesStaticType, Allocative)]
 #[starlark_docs(directory = "bxl")]
 #[repr(C)]
 pub(crate) enum StarlarkResultGen<T> {
@@ -99,16 +193,67 @@ pub(crate) type FrozenStarlarkResult = StarlarkResultGen<FrozenValue>;
 
 starlark_complex_values!(StarlarkResult);
 
+/// Serializes as `{"ok": <value>}` or `{"error": {...}}`, so BXL scripts can write fallible
+/// results via `ctx.output.write_json` without unwrapping them first.
+impl<T: Serialize> Serialize for StarlarkResultGen<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self {
+            StarlarkResultGen::Ok(val) => map.serialize_entry("ok", val)?,
+            StarlarkResultGen::Err(err) => {
+                map.serialize_entry("error", &StarlarkError { err: err.dupe() })?
+            }
+        }
+        map.end()
+    }
+}
+
+/// Renders a `buck2_error::Error` in either compact (single-line, debug-repr) or multiline
+/// form. Multiline form prints the error's source location (when it has one) followed by a
+/// caret line underlining the whole location string -- `buck2_error` only gives us the location
+/// as an opaque string, not a span within it, so this is a whole-string underline rather than
+/// rustc-style pointing at a specific sub-span -- then the error chain, one cause per line,
+/// indented by depth.
+fn render_error(err: &buck2_error::Error, multiline: bool) -> String {
+    if !multiline {
+        return format!("{:?}", err);
+    }
+
+    let mut out = String::new();
+    if let Some(loc) = err.source_location() {
+        out.push_str(&format!("--> {}\n", loc));
+        out.push_str(" |\n");
+        out.push_str(&format!(" | {}\n", "^".repeat(loc.len().max(1))));
+    }
+    for (depth, cause) in err.chain().enumerate() {
+        if depth > 0 {
+            out.push('\n');
+        }
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(&cause.to_string());
+    }
+    out
+}
+
 impl<T: Display> Display for StarlarkResultGen<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             StarlarkResultGen::Ok(val) => fmt_container(f, "Result(Ok = ", ")", [val]),
+            // The alternate ("{:#}") formatter requests multiline rendering, which is real
+            // newlines and indentation -- write it straight into the formatter rather than
+            // through `StarlarkStr::repr`, which would escape those newlines into literal
+            // `\n` and defeat the whole point of asking for multiline output.
+            StarlarkResultGen::Err(err) if f.alternate() => {
+                write!(f, "Result(Err = {})", render_error(err, true))
+            }
             StarlarkResultGen::Err(err) => fmt_container(
                 f,
                 "Result(Err = ",
                 ")",
-                // TODO(nero): implement multiline when multiline is requested
-                [StarlarkStr::repr(&format!("{:?}", err))],
+                [StarlarkStr::repr(&render_error(err, false))],
             ),
         }
     }
@@ -157,6 +302,121 @@ fn result_methods(builder: &mut MethodsBuilder) {
             either::Either::Right(x) => x.unwrap_err(),
         }
     }
+
+    /// If the result is `Ok`, applies `func` to the inner value and returns a new `bxl.Result`
+    /// wrapping the outcome (an `Err` raised by `func` is captured rather than propagated).
+    /// If the result is `Err`, returns it unchanged.
+    fn map<'v>(
+        this: ValueTypedComplex<'v, StarlarkResult<'v>>,
+        #[starlark(require = pos)] func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match this.unpack() {
+            either::Either::Left(x) => x.map(func, eval),
+            either::Either::Right(x) => x.map(func, eval),
+        }
+    }
+
+    /// If the result is `Err`, applies `func` to the inner error and returns a new `bxl.Result`
+    /// wrapping the outcome. If the result is `Ok`, returns it unchanged.
+    fn map_err<'v>(
+        this: ValueTypedComplex<'v, StarlarkResult<'v>>,
+        #[starlark(require = pos)] func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match this.unpack() {
+            either::Either::Left(x) => x.map_err(func, eval),
+            either::Either::Right(x) => x.map_err(func, eval),
+        }
+    }
+
+    /// If the result is `Ok`, calls `func` with the inner value; `func` must itself return a
+    /// `bxl.Result`, which becomes the result of this call (an `Err` raised by `func` is
+    /// captured rather than propagated). If the result is `Err`, returns it unchanged.
+    /// Useful for chaining fallible operations.
+    fn and_then<'v>(
+        this: ValueTypedComplex<'v, StarlarkResult<'v>>,
+        #[starlark(require = pos)] func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match this.unpack() {
+            either::Either::Left(x) => x.and_then(func, eval),
+            either::Either::Right(x) => x.and_then(func, eval),
+        }
+    }
+
+    /// If the result is `Err`, calls `func` with the inner error; `func` must itself return a
+    /// `bxl.Result`, which becomes the result of this call (an `Err` raised by `func` is
+    /// captured rather than propagated). If the result is `Ok`, returns it unchanged.
+    fn or_else<'v>(
+        this: ValueTypedComplex<'v, StarlarkResult<'v>>,
+        #[starlark(require = pos)] func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match this.unpack() {
+            either::Either::Left(x) => x.or_else(func, eval),
+            either::Either::Right(x) => x.or_else(func, eval),
+        }
+    }
+
+    /// Returns the inner value if the result is `Ok`, otherwise returns `default`.
+    fn unwrap_or<'v>(
+        this: ValueTypedComplex<'v, StarlarkResult<'v>>,
+        #[starlark(require = pos)] default: Value<'v>,
+    ) -> anyhow::Result<Value<'v>> {
+        Ok(match this.unpack() {
+            either::Either::Left(x) => x.unwrap_or(default),
+            either::Either::Right(x) => x.unwrap_or(default),
+        })
+    }
+
+    /// Returns the inner value if the result is `Ok`, otherwise calls `func` with the inner
+    /// error and returns its result.
+    fn unwrap_or_else<'v>(
+        this: ValueTypedComplex<'v, StarlarkResult<'v>>,
+        #[starlark(require = pos)] func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<Value<'v>> {
+        match this.unpack() {
+            either::Either::Left(x) => x.unwrap_or_else(func, eval),
+            either::Either::Right(x) => x.unwrap_or_else(func, eval),
+        }
+    }
+
+    /// Returns the inner value if the result is `Ok`, or `None` if it is an `Err`.
+    fn ok<'v>(this: ValueTypedComplex<'v, StarlarkResult<'v>>) -> anyhow::Result<Value<'v>> {
+        Ok(match this.unpack() {
+            either::Either::Left(x) => x.ok(),
+            either::Either::Right(x) => x.ok(),
+        })
+    }
+}
+
+/// Converts a value returned from a `map_err`/`or_else`-style callback into a
+/// `buck2_error::Error`, preserving the original error's category/tags/source location
+/// losslessly when the callback returns a `bxl.Error` rather than collapsing it to a string.
+fn value_into_error<'v>(value: Value<'v>) -> buck2_error::Error {
+    match value.downcast_ref::<StarlarkError>() {
+        Some(err) => err.err.dupe(),
+        None => buck2_error::Error::from(anyhow::anyhow!("{}", value)),
+    }
+}
+
+/// Downcasts a value returned by a `bxl.Result` combinator callback, requiring it to itself
+/// be a `bxl.Result` (mirroring Rust's `Result::and_then`/`or_else` signatures).
+fn value_as_result<'v>(value: Value<'v>) -> anyhow::Result<StarlarkResult<'v>> {
+    let typed = ValueTypedComplex::<StarlarkResult<'v>>::new(value)
+        .ok_or_else(|| BxlResultError::ExpectedResult(value.to_repr()))?;
+    Ok(match typed.unpack() {
+        either::Either::Left(x) => match x {
+            StarlarkResultGen::Ok(v) => StarlarkResultGen::Ok(v.to_value()),
+            StarlarkResultGen::Err(e) => StarlarkResultGen::Err(e.dupe()),
+        },
+        either::Either::Right(x) => match x {
+            StarlarkResultGen::Ok(v) => StarlarkResultGen::Ok(v.to_value()),
+            StarlarkResultGen::Err(e) => StarlarkResultGen::Err(e.dupe()),
+        },
+    })
 }
 
 impl<T> StarlarkResultGen<T> {
@@ -192,4 +452,144 @@ impl<'v, V: ValueLike<'v>> StarlarkResultGen<V> {
             StarlarkResultGen::Err(err) => Ok(StarlarkError { err: err.dupe() }),
         }
     }
-}
\ No newline at end of file
+
+    fn map(
+        &self,
+        func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match self {
+            StarlarkResultGen::Ok(val) => Ok(StarlarkResultGen::from_result(
+                eval.eval_function(func.0, &[val.to_value()], &[]),
+            )),
+            StarlarkResultGen::Err(err) => Ok(StarlarkResultGen::Err(err.dupe())),
+        }
+    }
+
+    fn map_err(
+        &self,
+        func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match self {
+            StarlarkResultGen::Ok(val) => Ok(StarlarkResultGen::Ok(val.to_value())),
+            StarlarkResultGen::Err(err) => {
+                let err_value = eval.heap().alloc(StarlarkError { err: err.dupe() });
+                Ok(match eval.eval_function(func.0, &[err_value], &[]) {
+                    Ok(new_err) => StarlarkResultGen::Err(value_into_error(new_err)),
+                    Err(e) => StarlarkResultGen::from_result(Err(e)),
+                })
+            }
+        }
+    }
+
+    fn and_then(
+        &self,
+        func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match self {
+            StarlarkResultGen::Ok(val) => {
+                match eval.eval_function(func.0, &[val.to_value()], &[]) {
+                    Ok(ret) => value_as_result(ret),
+                    Err(e) => Ok(StarlarkResultGen::from_result(Err(e))),
+                }
+            }
+            StarlarkResultGen::Err(err) => Ok(StarlarkResultGen::Err(err.dupe())),
+        }
+    }
+
+    fn or_else(
+        &self,
+        func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        match self {
+            StarlarkResultGen::Ok(val) => Ok(StarlarkResultGen::Ok(val.to_value())),
+            StarlarkResultGen::Err(err) => {
+                let err_value = eval.heap().alloc(StarlarkError { err: err.dupe() });
+                match eval.eval_function(func.0, &[err_value], &[]) {
+                    Ok(ret) => value_as_result(ret),
+                    Err(e) => Ok(StarlarkResultGen::from_result(Err(e))),
+                }
+            }
+        }
+    }
+
+    fn unwrap_or(&self, default: Value<'v>) -> Value<'v> {
+        match self {
+            StarlarkResultGen::Ok(val) => val.to_value(),
+            StarlarkResultGen::Err(_) => default,
+        }
+    }
+
+    fn unwrap_or_else(
+        &self,
+        func: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<Value<'v>> {
+        match self {
+            StarlarkResultGen::Ok(val) => Ok(val.to_value()),
+            StarlarkResultGen::Err(err) => {
+                let err_value = eval.heap().alloc(StarlarkError { err: err.dupe() });
+                eval.eval_function(func.0, &[err_value], &[])
+            }
+        }
+    }
+
+    fn ok(&self) -> Value<'v> {
+        match self {
+            StarlarkResultGen::Ok(val) => val.to_value(),
+            StarlarkResultGen::Err(_) => Value::new_none(),
+        }
+    }
+}
+
+/// Members of the `bxl` namespace for constructing a `bxl.Result` from scratch, rather than just
+/// consuming ones the engine hands back from e.g. `ctx.lazy`.
+#[starlark_module]
+fn bxl_try_members(globals: &mut GlobalsBuilder) {
+    /// The equivalent of a `try { ... }` block: evaluate the zero-arg callable `f`, returning
+    /// `Ok(value)` if it returns normally or `Err` wrapping the raised error as a `bxl.Error`
+    /// if it raises. This lets BXL scripts run speculative analysis/query calls and recover,
+    /// which is otherwise impossible since any raised error terminates the script.
+    fn r#try<'v>(
+        #[starlark(require = pos)] f: StarlarkCallable<'v>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<StarlarkResult<'v>> {
+        Ok(StarlarkResultGen::from_result(eval.eval_function(
+            f.0,
+            &[],
+            &[],
+        )))
+    }
+}
+
+/// Registers `bxl.try`, nested under the `bxl` namespace alongside the rest of the `bxl.*`
+/// surface (`bxl.Result`, `bxl.Error`, ...), rather than as a bare top-level global.
+pub(crate) fn register_bxl_try(globals: &mut GlobalsBuilder) {
+    globals.struct_("bxl", bxl_try_members);
+}
+
+#[cfg(test)]
+mod tests {
+    use buck2_error::Error as BuckError;
+
+    use super::FrozenStarlarkResult;
+    use super::StarlarkResultGen;
+
+    #[test]
+    fn alternate_display_renders_real_newlines_not_escaped_ones() {
+        let err: FrozenStarlarkResult =
+            StarlarkResultGen::Err(BuckError::from(anyhow::anyhow!("boom").context("outer")));
+        let multiline = format!("{:#}", err);
+        assert!(
+            multiline.contains('\n'),
+            "expected the alternate formatter to produce real newlines, got: {multiline:?}"
+        );
+        assert!(
+            !multiline.contains("\\n"),
+            "expected the alternate formatter not to escape newlines via StarlarkStr::repr, got: {multiline:?}"
+        );
+    }
+}